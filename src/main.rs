@@ -3,19 +3,22 @@
 //! Provides information on GCC's supported architectures.
 
 use bitvec::prelude as bv;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use libc::EXIT_FAILURE;
 use nom::{
+    branch::alt,
     bytes::complete::{tag, take},
-    character::complete::{alphanumeric1, multispace0},
-    combinator::{map, recognize},
+    character::complete::{alphanumeric1, char as nom_char, digit1, multispace0},
+    combinator::{all_consuming, map, map_res, recognize},
     error::ParseError,
-    multi::fold_many_m_n,
-    sequence::{delimited, separated_pair},
+    multi::{fold_many_m_n, many0},
+    sequence::{delimited, preceded, separated_pair},
     IResult,
 };
 use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
+use serde::Serialize;
 use std::{
+    collections::{BTreeMap, HashMap},
     convert::TryFrom as _,
     fmt::{self, Display},
     io::{self, Write},
@@ -47,19 +50,49 @@ fn run() -> Result<()> {
     exclusion_check(args)?;
 
     if args.arch.is_empty().not() {
-        return report_arch(&args.arch, &arch_db);
+        if args.triples {
+            return report_triples(&args.arch, &arch_db);
+        }
+
+        return report_arch(&args.arch, &arch_db, args.format);
+    }
+
+    if args.meta.is_empty().not() {
+        return report_meta(&args.meta, &arch_db);
     }
 
     if args.feat.is_empty().not() {
-        return report_feat(&args.feat, &arch_db);
+        return report_feat(&args.feat, &arch_db, args.format, args.endian);
+    }
+
+    if args.query.is_empty().not() {
+        return report_query(&args.query, &arch_db, args.endian);
+    }
+
+    if args.similar.is_empty().not() {
+        return report_similar(&args.similar, args.top, &arch_db);
+    }
+
+    if args.triple.is_empty().not() {
+        let arch_name =
+            arch_from_triple(&args.triple).ok_or_else(|| Error::unknown_triple(&args.triple))?;
+        return report_arch(arch_name, &arch_db, args.format);
+    }
+
+    if args.compare.is_empty().not() {
+        return report_compare(&args.compare[0], &args.compare[1], &arch_db);
+    }
+
+    if let Some(endian) = args.endian {
+        return report_endian(endian, &arch_db);
     }
 
     if args.archs {
-        return print_all_archs(&arch_db);
+        return print_all_archs(&arch_db, args.format);
     }
 
     if args.feats {
-        return print_all_feats(&arch_db);
+        return print_all_feats(&arch_db, args.format);
     }
 
     Err(Error::NothingRequested)
@@ -74,11 +107,36 @@ fn exclusion_check(args: &Args) -> Result<()> {
         offenders.push("--arch");
     }
 
+    if args.meta.is_empty().not() {
+        count += 1;
+        offenders.push("--meta");
+    }
+
     if args.feat.is_empty().not() {
         count += 1;
         offenders.push("--feat");
     }
 
+    if args.query.is_empty().not() {
+        count += 1;
+        offenders.push("--query");
+    }
+
+    if args.similar.is_empty().not() {
+        count += 1;
+        offenders.push("--similar");
+    }
+
+    if args.triple.is_empty().not() {
+        count += 1;
+        offenders.push("--triple");
+    }
+
+    if args.compare.is_empty().not() {
+        count += 1;
+        offenders.push("--compare");
+    }
+
     if args.archs {
         count += 1;
         offenders.push("--archs");
@@ -99,76 +157,464 @@ fn exclusion_check(args: &Args) -> Result<()> {
 }
 
 /// Report on the selected architecture.
-fn report_arch(arch_name: &str, arch_db: &[Arch]) -> Result<()> {
+fn report_arch(arch_name: &str, arch_db: &[Arch], format: Format) -> Result<()> {
     // Get the info for the selected architecture.
     let arch = arch_db
         .iter()
         .find(|arch| arch.name == arch_name)
         .ok_or_else(|| Error::unknown_arch(arch_name))?;
 
+    render(&Output::Arch(arch), format)
+}
+
+/// Print all the known architectures.
+fn print_all_archs(arch_db: &[Arch], format: Format) -> Result<()> {
+    render(&Output::AllArchs(arch_db.iter().collect()), format)
+}
+
+/// Report on the selected feature.
+fn report_feat(
+    feat_name: &str,
+    arch_db: &[Arch],
+    format: Format,
+    endian: Option<Endianness>,
+) -> Result<()> {
+    let feat = Feat::from_str(feat_name)?;
+
+    let names = arch_db
+        .iter()
+        .filter(|arch| arch.has_feature(feat))
+        .filter(|arch| matches_endian(arch, endian))
+        .map(|arch| arch.name)
+        .collect();
+
+    render(&Output::ArchNames(names), format)
+}
+
+/// Report architectures matching a boolean feature-query expression.
+fn report_query(query: &str, arch_db: &[Arch], endian: Option<Endianness>) -> Result<()> {
+    let expr = parse_query(query)?;
+
+    let mut o = io::stdout();
+
+    for arch in arch_db {
+        if eval_query(&expr, arch) && matches_endian(arch, endian) {
+            writeln!(o, "{}", arch.name)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the endianness and word size known for an architecture.
+fn report_meta(arch_name: &str, arch_db: &[Arch]) -> Result<()> {
+    let arch = arch_db
+        .iter()
+        .find(|arch| arch.name == arch_name)
+        .ok_or_else(|| Error::unknown_arch(arch_name))?;
+
     let mut o = io::stdout();
 
-    for idx in arch.info.0.iter_ones() {
-        let feat = Feat::try_from(idx as u8)?;
-        writeln!(o, "{}", feat)?;
+    match arch.meta {
+        Some(meta) => {
+            writeln!(o, "endianness: {}", meta.endianness)?;
+            writeln!(o, "word_bits: {}", meta.word_bits)?;
+        }
+        None => writeln!(o, "no metadata known for '{}'", arch_name)?,
     }
 
     Ok(())
 }
 
-/// Print all the known architectures.
-fn print_all_archs(arch_db: &[Arch]) -> Result<()> {
+/// List architectures matching an endianness constraint.
+fn report_endian(endian: Endianness, arch_db: &[Arch]) -> Result<()> {
     let mut o = io::stdout();
 
-    for arch in arch_db {
+    for arch in arch_db
+        .iter()
+        .filter(|arch| matches_endian(arch, Some(endian)))
+    {
         writeln!(o, "{}", arch.name)?;
     }
 
     Ok(())
 }
 
-/// Report on the selected feature.
-fn report_feat(feat_name: &str, arch_db: &[Arch]) -> Result<()> {
-    let feat = Feat::from_str(feat_name)?;
+/// Check whether an architecture's metadata satisfies an optional endianness constraint.
+fn matches_endian(arch: &Arch, endian: Option<Endianness>) -> bool {
+    endian.is_none_or(|wanted| arch.meta.is_some_and(|meta| meta.endianness == wanted))
+}
 
-    let arch_iter = arch_db.iter().filter(|arch| {
-        let val = arch
-            .info
-            .0
-            .get(feat as usize)
-            .map(|val| *val as i32)
-            .unwrap_or(0);
+/// Evaluate a query expression against an architecture's feature bits.
+fn eval_query(expr: &Expr, arch: &Arch) -> bool {
+    match expr {
+        Expr::And(lhs, rhs) => eval_query(lhs, arch) && eval_query(rhs, arch),
+        Expr::Or(lhs, rhs) => eval_query(lhs, arch) || eval_query(rhs, arch),
+        Expr::Not(inner) => eval_query(inner, arch).not(),
+        Expr::Leaf(feat) => {
+            // The `Ignore` slot is a placeholder, not a real fact, so it never matches.
+            *feat != Feat::Ignore && *arch.info.0.get(*feat as usize).unwrap()
+        }
+    }
+}
 
-        val == 1
-    });
+/// Rank architectures by feature-bit similarity to a given one.
+fn report_similar(arch_name: &str, top: Option<usize>, arch_db: &[Arch]) -> Result<()> {
+    let query = arch_db
+        .iter()
+        .find(|arch| arch.name == arch_name)
+        .ok_or_else(|| Error::unknown_arch(arch_name))?;
+
+    let mut ranked: Vec<(usize, ArchName)> = arch_db
+        .iter()
+        .filter(|arch| arch.name != arch_name)
+        .map(|arch| (hamming_distance(&query.info, &arch.info), arch.name))
+        .collect();
+
+    ranked
+        .sort_by(|(dist_a, name_a), (dist_b, name_b)| dist_a.cmp(dist_b).then(name_a.cmp(name_b)));
 
     let mut o = io::stdout();
 
-    for arch in arch_iter {
-        writeln!(o, "{}", arch.name)?;
+    for (distance, name) in ranked.into_iter().take(top.unwrap_or(usize::MAX)) {
+        writeln!(o, "{}  {}", distance, name)?;
+    }
+
+    Ok(())
+}
+
+/// Count the feature bits that differ between two architectures, ignoring the `Ignore` slot.
+fn hamming_distance(a: &ArchInfo, b: &ArchInfo) -> usize {
+    (0..NUM_FIELDS)
+        .filter(|&idx| idx != Feat::Ignore as usize)
+        .filter(|&idx| a.0[idx] != b.0[idx])
+        .count()
+}
+
+/// Print the target triples known to lower to an architecture's GCC port.
+fn report_triples(arch_name: &str, arch_db: &[Arch]) -> Result<()> {
+    // Confirm the architecture is actually known before reporting on its triples.
+    arch_db
+        .iter()
+        .find(|arch| arch.name == arch_name)
+        .ok_or_else(|| Error::unknown_arch(arch_name))?;
+
+    let mut o = io::stdout();
+
+    for triple in triples_for_arch(arch_name) {
+        writeln!(o, "{}", triple)?;
+    }
+
+    Ok(())
+}
+
+/// The canonical target triples known to lower to each GCC architecture port.
+///
+/// This is a curated, non-exhaustive table covering the architectures most
+/// commonly seen in cross-compilation target lists. It's the single source of
+/// truth for both `triples_for_arch` and `arch_from_triple`, so the forward
+/// and reverse lookups can never desync from each other.
+const TRIPLE_TABLE: &[(ArchName, &[&str])] = &[
+    (
+        "aarch64",
+        &[
+            "aarch64-unknown-linux-gnu",
+            "aarch64-unknown-linux-musl",
+            "aarch64-apple-darwin",
+        ],
+    ),
+    (
+        "arm",
+        &[
+            "armv7-unknown-linux-gnueabihf",
+            "thumbv7m-linux-eabi",
+            "thumbv7em-none-eabi",
+        ],
+    ),
+    (
+        "i386",
+        &[
+            "i686-unknown-linux-gnu",
+            "x86_64-unknown-linux-gnu",
+            "x86_64-unknown-linux-musl",
+            "x86_64-musl",
+            "x86_64-apple-darwin",
+        ],
+    ),
+    ("mips", &["mips-unknown-linux-gnu", "mipsel-unknown-linux-gnu"]),
+    (
+        "powerpc",
+        &[
+            "powerpc-unknown-linux-gnu",
+            "powerpc64-unknown-linux-gnu",
+            "powerpc64le-unknown-linux-gnu",
+        ],
+    ),
+    (
+        "riscv",
+        &["riscv32gc-unknown-linux-gnu", "riscv64gc-unknown-linux-gnu"],
+    ),
+    ("s390", &["s390x-unknown-linux-gnu"]),
+    (
+        "sparc",
+        &["sparc-unknown-linux-gnu", "sparc64-unknown-linux-gnu"],
+    ),
+];
+
+/// Look up the canonical target triples known to lower to a GCC architecture port.
+fn triples_for_arch(arch_name: &str) -> &'static [&'static str] {
+    TRIPLE_TABLE
+        .iter()
+        .find(|(name, _)| *name == arch_name)
+        .map_or(&[], |(_, triples)| triples)
+}
+
+/// Reverse-resolve a target triple to the GCC architecture port it lowers to.
+fn arch_from_triple(triple: &str) -> Option<ArchName> {
+    TRIPLE_TABLE
+        .iter()
+        .find(|(_, triples)| triples.contains(&triple))
+        .map(|(name, _)| *name)
+}
+
+/// Report the features two architectures share, and the features unique to each.
+fn report_compare(arch_a_name: &str, arch_b_name: &str, arch_db: &[Arch]) -> Result<()> {
+    let arch_a = arch_db
+        .iter()
+        .find(|arch| arch.name == arch_a_name)
+        .ok_or_else(|| Error::unknown_arch(arch_a_name))?;
+
+    let arch_b = arch_db
+        .iter()
+        .find(|arch| arch.name == arch_b_name)
+        .ok_or_else(|| Error::unknown_arch(arch_b_name))?;
+
+    let shared =
+        known_feats().filter(|feat| arch_a.has_feature(*feat) && arch_b.has_feature(*feat));
+    let only_a =
+        known_feats().filter(|feat| arch_a.has_feature(*feat) && !arch_b.has_feature(*feat));
+    let only_b =
+        known_feats().filter(|feat| !arch_a.has_feature(*feat) && arch_b.has_feature(*feat));
+
+    let mut o = io::stdout();
+
+    writeln!(o, "shared:")?;
+    for feat in shared {
+        writeln!(o, "{}", feat)?;
+    }
+
+    writeln!(o, "only {}:", arch_a.name)?;
+    for feat in only_a {
+        writeln!(o, "{}", feat)?;
+    }
+
+    writeln!(o, "only {}:", arch_b.name)?;
+    for feat in only_b {
+        writeln!(o, "{}", feat)?;
     }
 
     Ok(())
 }
 
 /// Print all known features.
-fn print_all_feats(_arch_db: &[Arch]) -> Result<()> {
+fn print_all_feats(_arch_db: &[Arch], format: Format) -> Result<()> {
+    render(&Output::Feats, format)
+}
+
+/// A reporting mode's output, prior to being rendered in the requested format.
+enum Output<'a> {
+    /// The full feature breakdown of a single architecture (`--arch`).
+    Arch(&'a Arch),
+
+    /// A plain list of architecture names (`--feat`).
+    ArchNames(Vec<ArchName>),
+
+    /// Every architecture (`--archs`). JSON and CSV output include each
+    /// architecture's features; text output lists only the names.
+    AllArchs(Vec<&'a Arch>),
+
+    /// Every known feature, with its description (`--feats`).
+    Feats,
+}
+
+/// Render an `Output` in the requested format and print it to stdout.
+fn render(output: &Output, format: Format) -> Result<()> {
     let mut o = io::stdout();
 
-    for idx in 0..NUM_FIELDS {
-        let feat = Feat::try_from(idx as u8).unwrap();
+    match format {
+        Format::Text => render_text(output, &mut o),
+        Format::Json => render_json(output, &mut o),
+        Format::Csv => render_csv(output, &mut o),
+    }
+}
+
+/// Render an `Output` as human-readable plaintext.
+fn render_text(output: &Output, o: &mut impl Write) -> Result<()> {
+    match output {
+        Output::Arch(arch) => {
+            for feat in known_feats().filter(|feat| arch.has_feature(*feat)) {
+                writeln!(o, "{}", feat)?;
+            }
+        }
+        Output::ArchNames(names) => {
+            for name in names {
+                writeln!(o, "{}", name)?;
+            }
+        }
+        Output::AllArchs(archs) => {
+            for arch in archs {
+                writeln!(o, "{}", arch.name)?;
+            }
+        }
+        Output::Feats => {
+            for feat in known_feats() {
+                writeln!(o, "{}", feat)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Render an `Output` as JSON.
+fn render_json(output: &Output, o: &mut impl Write) -> Result<()> {
+    match output {
+        Output::Arch(arch) => {
+            let features = known_feats()
+                .map(|feat| (feat.short_code(), arch.has_feature(feat)))
+                .collect();
+
+            serde_json::to_writer(
+                &mut *o,
+                &ArchFeatures {
+                    name: arch.name,
+                    features,
+                },
+            )?;
+        }
+        Output::ArchNames(names) => serde_json::to_writer(&mut *o, names)?,
+        Output::AllArchs(archs) => {
+            let entries: Vec<ArchFeatures> = archs
+                .iter()
+                .map(|arch| {
+                    let features = known_feats()
+                        .map(|feat| (feat.short_code(), arch.has_feature(feat)))
+                        .collect();
+
+                    ArchFeatures {
+                        name: arch.name,
+                        features,
+                    }
+                })
+                .collect();
+
+            serde_json::to_writer(&mut *o, &entries)?;
+        }
+        Output::Feats => {
+            let entries: Vec<FeatEntry> = known_feats()
+                .map(|feat| FeatEntry {
+                    code: feat.short_code(),
+                    description: feat.description(),
+                })
+                .collect();
+
+            serde_json::to_writer(&mut *o, &entries)?;
+        }
+    }
+
+    writeln!(o)?;
 
-        if feat != Feat::Ignore {
-            writeln!(o, "{}", feat)?;
+    Ok(())
+}
+
+/// Render an `Output` as CSV.
+fn render_csv(output: &Output, o: &mut impl Write) -> Result<()> {
+    match output {
+        Output::Arch(arch) => {
+            let codes: Vec<&str> = known_feats().map(|feat| feat.short_code()).collect();
+            writeln!(o, "{}", codes.join(","))?;
+
+            let row: Vec<&str> = known_feats()
+                .map(|feat| if arch.has_feature(feat) { "1" } else { "0" })
+                .collect();
+            writeln!(o, "{}", row.join(","))?;
+        }
+        Output::ArchNames(names) => {
+            for name in names {
+                writeln!(o, "{}", csv_field(name))?;
+            }
+        }
+        Output::AllArchs(archs) => {
+            let codes: Vec<&str> = known_feats().map(|feat| feat.short_code()).collect();
+            writeln!(o, "{}", codes.join(","))?;
+
+            for arch in archs {
+                let row: Vec<&str> = known_feats()
+                    .map(|feat| if arch.has_feature(feat) { "1" } else { "0" })
+                    .collect();
+                writeln!(o, "{}", row.join(","))?;
+            }
+        }
+        Output::Feats => {
+            writeln!(o, "code,description")?;
+
+            for feat in known_feats() {
+                writeln!(o, "{},{}", feat.short_code(), csv_field(feat.description()))?;
+            }
         }
     }
 
     Ok(())
 }
 
-/// Load the architecture info and parse it.
+/// Iterate over every real feature, in bit order, skipping the `Ignore` placeholder.
+fn known_feats() -> impl Iterator<Item = Feat> {
+    (0..NUM_FIELDS)
+        .map(|idx| Feat::try_from(idx as u8).unwrap())
+        .filter(|feat| *feat != Feat::Ignore)
+}
+
+/// Quote a CSV field if it contains a comma, quote, or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// A single architecture's features, keyed by short code, for JSON output.
+#[derive(Serialize)]
+struct ArchFeatures {
+    /// The name of the architecture.
+    name: ArchName,
+
+    /// Whether the architecture has each feature, keyed by short code.
+    features: BTreeMap<&'static str, bool>,
+}
+
+/// A single feature's short code and description, for JSON output.
+#[derive(Serialize)]
+struct FeatEntry {
+    /// The feature's short code.
+    code: &'static str,
+
+    /// The feature's human-readable description.
+    description: &'static str,
+}
+
+/// Load the architecture info and parse it, attaching metadata where known.
 fn load_arch_info() -> Result<Vec<Arch>> {
-    raw_arch_info().map(parse_arch_line).collect()
+    let meta_table = load_arch_meta()?;
+
+    raw_arch_info()
+        .map(parse_arch_line)
+        .map(|arch| {
+            let mut arch = arch?;
+            arch.meta = meta_table.get(arch.name).copied();
+            Ok(arch)
+        })
+        .collect()
 }
 
 /// Load architecture info from the arch file as an iterator over the lines.
@@ -180,11 +626,59 @@ fn raw_arch_info() -> impl Iterator<Item = &'static str> {
 fn parse_arch_line(input: &'static str) -> Result<Arch> {
     Ok(map(
         separated_pair(parse_arch_name, tag("| "), parse_arch_info),
-        |(name, info)| Arch { name, info },
+        |(name, info)| Arch {
+            name,
+            info,
+            meta: None,
+        },
     )(input)?
     .1)
 }
 
+/// Load the architecture metadata side table and parse it, keyed by architecture name.
+fn load_arch_meta() -> Result<HashMap<ArchName, ArchMeta>> {
+    raw_arch_meta().map(parse_meta_line).collect()
+}
+
+/// Load architecture metadata from the metadata file as an iterator over the lines.
+fn raw_arch_meta() -> impl Iterator<Item = &'static str> {
+    include_str!("arch_meta.txt").lines()
+}
+
+/// Parse a single line of the metadata file into a name/metadata pair.
+fn parse_meta_line(input: &'static str) -> Result<(ArchName, ArchMeta)> {
+    Ok(map(
+        separated_pair(parse_arch_name, tag("| "), parse_arch_meta),
+        |(name, meta)| (name, meta),
+    )(input)?
+    .1)
+}
+
+/// Parse the endianness and word-size portion of a metadata line.
+fn parse_arch_meta(input: &'static str) -> IResult<&'static str, ArchMeta> {
+    map(
+        separated_pair(parse_endianness, multispace0, parse_word_bits),
+        |(endianness, word_bits)| ArchMeta {
+            endianness,
+            word_bits,
+        },
+    )(input)
+}
+
+/// Parse an endianness keyword.
+fn parse_endianness(input: &'static str) -> IResult<&'static str, Endianness> {
+    alt((
+        map(tag("little"), |_| Endianness::Little),
+        map(tag("big"), |_| Endianness::Big),
+        map(tag("bi"), |_| Endianness::Bi),
+    ))(input)
+}
+
+/// Parse a native word width, in bits.
+fn parse_word_bits(input: &'static str) -> IResult<&'static str, u32> {
+    map_res(digit1, str::parse)(input)
+}
+
 /// Parse the architecture name, ignoring whitespace.
 fn parse_arch_name(input: &'static str) -> IResult<&'static str, ArchName> {
     ws(recognize(alphanumeric1))(input)
@@ -227,6 +721,110 @@ where
     delimited(multispace0, inner, multispace0)
 }
 
+/// Parse a full boolean feature-query expression, resolving short codes along the way.
+fn parse_query(input: &str) -> Result<Expr> {
+    // The rest of this file treats strings as `'static` (they come from the
+    // statically-included arch file); leak the query string to match, so the nom
+    // parsers and the `Error::BadParse` conversion can stay uniform.
+    let input: &'static str = Box::leak(input.to_owned().into_boxed_str());
+    let (_, raw) = all_consuming(ws(parse_or))(input)?;
+    resolve_query(raw)
+}
+
+/// Resolve every leaf in a raw expression into a `Feat`, failing on an unknown short code.
+fn resolve_query(raw: RawExpr) -> Result<Expr> {
+    Ok(match raw {
+        RawExpr::And(lhs, rhs) => Expr::And(
+            Box::new(resolve_query(*lhs)?),
+            Box::new(resolve_query(*rhs)?),
+        ),
+        RawExpr::Or(lhs, rhs) => Expr::Or(
+            Box::new(resolve_query(*lhs)?),
+            Box::new(resolve_query(*rhs)?),
+        ),
+        RawExpr::Not(inner) => Expr::Not(Box::new(resolve_query(*inner)?)),
+        RawExpr::Leaf(c) => {
+            let mut buf = [0u8; 4];
+            Expr::Leaf(Feat::from_str(c.encode_utf8(&mut buf))?)
+        }
+    })
+}
+
+/// Parse an `|`-separated disjunction, the lowest-precedence operator.
+fn parse_or(input: &str) -> IResult<&str, RawExpr> {
+    let (input, first) = parse_and(input)?;
+    let (input, rest) = many0(preceded(ws(nom_char('|')), parse_and))(input)?;
+    let expr = rest.into_iter().fold(first, |acc, next| {
+        RawExpr::Or(Box::new(acc), Box::new(next))
+    });
+    Ok((input, expr))
+}
+
+/// Parse a `&`-separated conjunction, binding tighter than `|`.
+fn parse_and(input: &str) -> IResult<&str, RawExpr> {
+    let (input, first) = parse_unary(input)?;
+    let (input, rest) = many0(preceded(ws(nom_char('&')), parse_unary))(input)?;
+    let expr = rest.into_iter().fold(first, |acc, next| {
+        RawExpr::And(Box::new(acc), Box::new(next))
+    });
+    Ok((input, expr))
+}
+
+/// Parse a unary `!` negation, binding tighter than `&`, or fall through to an atom.
+fn parse_unary(input: &str) -> IResult<&str, RawExpr> {
+    alt((
+        map(preceded(ws(nom_char('!')), parse_unary), |e| {
+            RawExpr::Not(Box::new(e))
+        }),
+        parse_atom,
+    ))(input)
+}
+
+/// Parse a parenthesized sub-expression or a bare leaf.
+fn parse_atom(input: &str) -> IResult<&str, RawExpr> {
+    alt((
+        delimited(ws(nom_char('(')), parse_or, ws(nom_char(')'))),
+        parse_leaf,
+    ))(input)
+}
+
+/// Parse a single feature short-code character.
+fn parse_leaf(input: &str) -> IResult<&str, RawExpr> {
+    map(ws(take(1usize)), |s: &str| {
+        RawExpr::Leaf(s.chars().next().unwrap())
+    })(input)
+}
+
+/// A feature-query expression prior to resolving each leaf's short code into a `Feat`.
+enum RawExpr {
+    /// A conjunction of two expressions.
+    And(Box<RawExpr>, Box<RawExpr>),
+
+    /// A disjunction of two expressions.
+    Or(Box<RawExpr>, Box<RawExpr>),
+
+    /// A negation of an expression.
+    Not(Box<RawExpr>),
+
+    /// A single feature short code, not yet resolved.
+    Leaf(char),
+}
+
+/// A boolean feature-query expression over resolved `Feat`s.
+enum Expr {
+    /// Both sub-expressions must hold.
+    And(Box<Expr>, Box<Expr>),
+
+    /// Either sub-expression must hold.
+    Or(Box<Expr>, Box<Expr>),
+
+    /// The sub-expression must not hold.
+    Not(Box<Expr>),
+
+    /// A single feature.
+    Leaf(Feat),
+}
+
 // Type definitions for an architecture entry, which
 // consists of the name of the architecture, and a bit
 // array representing the facts known about that architecture
@@ -239,11 +837,13 @@ struct Arch {
 
     /// The feature information for the architecture.
     info: ArchInfo,
+
+    /// Supplementary endianness/word-size metadata, if known.
+    meta: Option<ArchMeta>,
 }
 
 impl Arch {
     /// Get if an architecture supports a feature.
-    #[allow(unused)]
     fn has_feature(&self, feat: Feat) -> bool {
         // SAFETY: The `Feat` struct is smaller than the limit of the buffer.
         *self.info.0.get(feat as usize).unwrap()
@@ -265,6 +865,42 @@ type ArchInfoArray = bv::BitArr!(for NUM_FIELDS, in u8);
 /// The information known about an architecture by GCC.
 struct ArchInfo(ArchInfoArray);
 
+/// Non-boolean architecture facts not captured by GCC's boolean arch table,
+/// such as byte order and native word width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ArchMeta {
+    /// The architecture's byte order.
+    endianness: Endianness,
+
+    /// The architecture's native word width, in bits.
+    word_bits: u32,
+}
+
+/// An architecture's byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Endianness {
+    /// Most significant byte first.
+    Big,
+
+    /// Least significant byte first.
+    Little,
+
+    /// Can run in either byte order.
+    Bi,
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Endianness::Big => "big",
+            Endianness::Little => "little",
+            Endianness::Bi => "bi",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
 /// The different features supported by the architectures.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, TryFromPrimitive)]
 #[repr(u8)]
@@ -482,8 +1118,11 @@ enum Error {
     #[error("can't specify {offenders} together")]
     ConflictingArgs { offenders: String },
 
-    /// Indicates neither --arch or --feat were specified.
-    #[error("must specify either --arch or --feat")]
+    /// Indicates no reporting mode was selected.
+    #[error(
+        "must specify one of --arch, --meta, --feat, --query, --similar, --triple, --compare, \
+         --endian, --archs, or --feats"
+    )]
     NothingRequested,
 
     /// The name of the feat isn't recognized.
@@ -494,9 +1133,17 @@ enum Error {
     #[error("bad feat conversion")]
     BadFeatConversion(#[from] TryFromPrimitiveError<Feat>),
 
+    /// The triple doesn't resolve to a known architecture.
+    #[error("'{triple}' does not resolve to a known architecture")]
+    UnknownTriple { triple: String },
+
     /// Writing to stdout or stderr failed.
     #[error("failed to write output")]
     OutputFailed(#[from] io::Error),
+
+    /// Serializing output as JSON failed.
+    #[error("failed to serialize JSON output")]
+    JsonFailed(#[from] serde_json::Error),
 }
 
 impl Error {
@@ -513,6 +1160,13 @@ impl Error {
             feat_name: feat_name.into(),
         }
     }
+
+    /// Make an error for a triple that doesn't resolve to a known architecture.
+    fn unknown_triple(triple: &str) -> Error {
+        Error::UnknownTriple {
+            triple: triple.into(),
+        }
+    }
 }
 
 /// Simple program to greet a person
@@ -531,7 +1185,56 @@ struct Args {
     #[clap(short, long, default_value = "")]
     feat: String,
 
+    /// A boolean expression over feature short codes, e.g. `Q & !B & (a | t)`.
+    #[clap(short, long, default_value = "")]
+    query: String,
+
+    /// Find architectures most similar to this one, ranked by feature-bit distance.
+    #[clap(short, long, default_value = "")]
+    similar: String,
+
+    /// Limit `--similar` output to the top N closest architectures.
+    #[clap(short, long)]
+    top: Option<usize>,
+
+    /// List the known target triples for `--arch`, instead of its features.
+    #[clap(long)]
+    triples: bool,
+
+    /// A target triple to reverse-resolve to a GCC architecture port.
+    #[clap(short = 'T', long, default_value = "")]
+    triple: String,
+
+    /// Compare two architectures, reporting shared and unique features.
+    #[clap(short, long, num_args = 2, value_names = ["ARCH_A", "ARCH_B"])]
+    compare: Vec<String>,
+
     /// Print all the features.
     #[clap(short = 'F', long)]
     feats: bool,
+
+    /// The output format to render reporting modes in.
+    #[clap(long, value_enum, default_value = "text")]
+    format: Format,
+
+    /// Print the endianness and word size known for an architecture.
+    #[clap(short, long, default_value = "")]
+    meta: String,
+
+    /// Constrain `--feat`/`--query`, or list architectures, by endianness.
+    #[clap(short, long, value_enum)]
+    endian: Option<Endianness>,
+}
+
+/// An output format that a reporting mode can be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human-readable plaintext.
+    Text,
+
+    /// Machine-readable JSON.
+    Json,
+
+    /// Machine-readable CSV.
+    Csv,
 }